@@ -0,0 +1,225 @@
+/// Streaming object reads, modeled on how the lakeFS backend itself
+/// exposes reads as a plain HTTP body: instead of buffering an entire
+/// object into memory, callers get a `Stream<Item = Bytes>` they can drive
+/// incrementally, with support for byte-range (`Range`) requests so a
+/// parquet/ORC reader can fetch the footer first and the row groups after.
+///
+/// When lakeFS reports a physical (underlying-storage) address for the
+/// object, [`stream_object_preferring_physical_address`] streams straight
+/// from there instead of proxying the bytes through the lakeFS gateway,
+/// falling back to the gateway read when no physical address is available.
+use std::fmt;
+use std::sync::OnceLock;
+
+use bytes::Bytes;
+use futures::stream::{BoxStream, StreamExt, TryStreamExt};
+
+use crate::apis::configuration::Configuration;
+use crate::apis::{objects_api, Error as ApiError};
+
+/// A bare client, carrying none of `configuration.client`'s lakeFS
+/// credentials, for requests that go straight to underlying storage (the
+/// same reasoning [`crate::upload`] applies to staging PUTs).
+fn physical_storage_client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(reqwest::Client::new)
+}
+
+/// A byte range to request, in the same terms as the HTTP `Range` header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ByteRange {
+    pub offset: u64,
+    /// `None` means "to the end of the object".
+    pub length: Option<u64>,
+}
+
+impl ByteRange {
+    pub fn new(offset: u64, length: Option<u64>) -> Self {
+        ByteRange { offset, length }
+    }
+
+    fn header_value(&self) -> String {
+        match self.length {
+            Some(length) => format!("bytes={}-{}", self.offset, self.offset + length.saturating_sub(1)),
+            None => format!("bytes={}-", self.offset),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_value_with_explicit_length() {
+        assert_eq!(ByteRange::new(10, Some(100)).header_value(), "bytes=10-109");
+    }
+
+    #[test]
+    fn header_value_to_end_of_object() {
+        assert_eq!(ByteRange::new(10, None).header_value(), "bytes=10-");
+    }
+
+    #[test]
+    fn header_value_single_byte() {
+        assert_eq!(ByteRange::new(10, Some(1)).header_value(), "bytes=10-10");
+    }
+
+    #[test]
+    fn header_value_zero_length_requests_one_byte() {
+        // HTTP's `Range` header has no way to express an empty range, so a
+        // `length: Some(0)` collapses to a 1-byte request rather than zero
+        // bytes; callers that build a `ByteRange` from a computed size
+        // should treat 0 as "nothing to fetch" before reaching here.
+        assert_eq!(ByteRange::new(10, Some(0)).header_value(), "bytes=10-10");
+    }
+}
+
+/// Errors that can occur while streaming an object's bytes.
+#[derive(Debug)]
+pub enum DownloadError {
+    /// Fetching the object's physical address failed.
+    StatObject(ApiError<objects_api::StatObjectError>),
+    /// The HTTP request to fetch the bytes failed.
+    Request(reqwest::Error),
+    /// The server responded with a status this reader doesn't know how to
+    /// treat as a byte stream (neither `200 OK` nor `206 Partial Content`).
+    UnexpectedStatus(reqwest::StatusCode),
+    /// A byte range was requested but the server ignored it and returned
+    /// the full object instead of `206 Partial Content`.
+    RangeNotHonored,
+}
+
+impl fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DownloadError::StatObject(e) => write!(f, "failed to resolve object: {}", e),
+            DownloadError::Request(e) => write!(f, "object read failed: {}", e),
+            DownloadError::UnexpectedStatus(status) => write!(f, "unexpected object read status: {}", status),
+            DownloadError::RangeNotHonored => write!(f, "server ignored the requested byte range"),
+        }
+    }
+}
+
+impl std::error::Error for DownloadError {}
+
+/// A stream of an object's bytes, in request order, fetched one HTTP
+/// response body at a time.
+pub type ObjectByteStream = BoxStream<'static, Result<Bytes, DownloadError>>;
+
+/// Stream `path`'s bytes (optionally restricted to `range`) through the
+/// lakeFS gateway.
+pub async fn stream_object(
+    configuration: &Configuration,
+    repository: &str,
+    reference: &str,
+    path: &str,
+    range: Option<ByteRange>,
+) -> Result<ObjectByteStream, DownloadError> {
+    let url = gateway_object_url(configuration, repository, reference, path);
+    fetch_gateway_stream(configuration, &url, range).await
+}
+
+/// Like [`stream_object`], but when lakeFS reports a physical address for
+/// the object, stream directly from the underlying storage instead of
+/// proxying through the lakeFS gateway. Falls back to [`stream_object`]
+/// when no physical address is available.
+pub async fn stream_object_preferring_physical_address(
+    configuration: &Configuration,
+    repository: &str,
+    reference: &str,
+    path: &str,
+    range: Option<ByteRange>,
+) -> Result<ObjectByteStream, DownloadError> {
+    let stat = objects_api::stat_object(configuration, repository, reference, path, None, Some(true))
+        .await
+        .map_err(DownloadError::StatObject)?;
+
+    match stat.physical_address {
+        // Direct-to-storage reads must never carry the lakeFS client's own
+        // credentials, so this goes through a bare client rather than
+        // `configuration.client`.
+        Some(physical) => fetch_physical_stream(&physical.location, range).await,
+        None => stream_object(configuration, repository, reference, path, range).await,
+    }
+}
+
+fn gateway_object_url(configuration: &Configuration, repository: &str, reference: &str, path: &str) -> String {
+    format!(
+        "{}/repositories/{}/refs/{}/objects?path={}",
+        configuration.base_path.trim_end_matches('/'),
+        percent_encoding::utf8_percent_encode(repository, percent_encoding::NON_ALPHANUMERIC),
+        percent_encoding::utf8_percent_encode(reference, percent_encoding::NON_ALPHANUMERIC),
+        percent_encoding::utf8_percent_encode(path, percent_encoding::NON_ALPHANUMERIC),
+    )
+}
+
+/// Stream from the lakeFS gateway, carrying the same per-request
+/// credentials the generated `objects_api` functions attach. This request
+/// is hand-built (rather than routed through `objects_api::get_object`)
+/// so it can return a stream instead of buffering the whole body, but it
+/// still needs the same auth those generated functions apply.
+async fn fetch_gateway_stream(
+    configuration: &Configuration,
+    url: &str,
+    range: Option<ByteRange>,
+) -> Result<ObjectByteStream, DownloadError> {
+    let mut request = apply_auth(configuration, configuration.client.get(url));
+    if let Some(range) = range {
+        request = request.header(reqwest::header::RANGE, range.header_value());
+    }
+    send_and_stream(request, range).await
+}
+
+/// Stream directly from an underlying-storage physical address. Uses a
+/// bare client, deliberately carrying none of `configuration`'s lakeFS
+/// credentials (the same reasoning [`crate::upload`] applies to staging
+/// PUTs).
+async fn fetch_physical_stream(url: &str, range: Option<ByteRange>) -> Result<ObjectByteStream, DownloadError> {
+    let mut request = physical_storage_client().get(url);
+    if let Some(range) = range {
+        request = request.header(reqwest::header::RANGE, range.header_value());
+    }
+    send_and_stream(request, range).await
+}
+
+/// Attach the same per-request credentials the generated API functions
+/// apply from `configuration` (bearer token, basic auth, API key).
+fn apply_auth(configuration: &Configuration, mut request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    if let Some(ref api_key) = configuration.api_key {
+        let value = match api_key.prefix {
+            Some(ref prefix) => format!("{} {}", prefix, api_key.key),
+            None => api_key.key.clone(),
+        };
+        request = request.header(reqwest::header::AUTHORIZATION, value);
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        request = request.bearer_auth(token);
+    }
+    if let Some(ref auth) = configuration.basic_auth {
+        request = request.basic_auth(auth.0.clone(), auth.1.clone());
+    }
+    request
+}
+
+async fn send_and_stream(
+    request: reqwest::RequestBuilder,
+    range: Option<ByteRange>,
+) -> Result<ObjectByteStream, DownloadError> {
+    let response = request.send().await.map_err(DownloadError::Request)?;
+    let status = response.status();
+    if status == reqwest::StatusCode::PARTIAL_CONTENT {
+        return Ok(response.bytes_stream().map_err(DownloadError::Request).boxed());
+    }
+    if status == reqwest::StatusCode::OK {
+        if range.is_some() {
+            // We asked for a slice and got the whole object back: treating
+            // this as success would silently hand the caller the wrong
+            // bytes (e.g. a parquet footer read that's actually the file
+            // header), so this must be a hard error instead.
+            return Err(DownloadError::RangeNotHonored);
+        }
+        return Ok(response.bytes_stream().map_err(DownloadError::Request).boxed());
+    }
+    Err(DownloadError::UnexpectedStatus(status))
+}
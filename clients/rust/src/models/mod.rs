@@ -0,0 +1,2 @@
+pub mod storage_uri;
+pub use self::storage_uri::{StorageScheme, StorageUri, StorageUriParseError};
@@ -25,3 +25,296 @@ impl StorageUri {
         }
     }
 }
+
+// -- Hand-written extensions below: typed access to `location`. ------------
+//
+// `location` is the raw string lakeFS returns (e.g. "s3://bucket/key",
+// "gs://bucket/key", "https://account.blob.core.windows.net/container/key").
+// These helpers parse it into a scheme/bucket/key triple so callers don't
+// each have to re-implement that parsing by hand.
+
+use std::fmt;
+
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+const KEY_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'/')
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// The storage provider a [`StorageUri`] location addresses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StorageScheme {
+    S3,
+    Gcs,
+    AzureBlob { account: String },
+    Local,
+    /// An S3-compatible store reached through a custom endpoint (MinIO, R2, ...).
+    Custom { endpoint: String },
+}
+
+/// `location` parsed into its scheme, bucket/container and object key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ParsedLocation {
+    scheme: StorageScheme,
+    bucket: Option<String>,
+    key: String,
+}
+
+/// Error returned when a [`StorageUri`] location cannot be parsed or does
+/// not name a supported storage provider.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StorageUriParseError {
+    /// `location` was empty.
+    Empty,
+    /// `location` used a scheme this client does not know how to address.
+    UnsupportedScheme(String),
+    /// `location` had a recognized scheme but no bucket/container segment.
+    MissingBucket,
+    /// The key portion of `location` was not validly percent-encoded.
+    InvalidEncoding,
+}
+
+impl fmt::Display for StorageUriParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageUriParseError::Empty => write!(f, "storage uri location is empty"),
+            StorageUriParseError::UnsupportedScheme(scheme) => {
+                write!(f, "unsupported storage uri scheme: {}", scheme)
+            }
+            StorageUriParseError::MissingBucket => write!(f, "storage uri is missing a bucket/container"),
+            StorageUriParseError::InvalidEncoding => write!(f, "storage uri key is not validly percent-encoded"),
+        }
+    }
+}
+
+impl std::error::Error for StorageUriParseError {}
+
+impl StorageUri {
+    /// Parse and validate `location`, returning a [`StorageUri`] only if it
+    /// names a supported storage provider.
+    pub fn parse(location: impl Into<String>) -> Result<StorageUri, StorageUriParseError> {
+        let location = location.into();
+        parse_location(&location)?;
+        Ok(StorageUri { location })
+    }
+
+    /// The storage provider this location addresses.
+    pub fn scheme(&self) -> Result<StorageScheme, StorageUriParseError> {
+        parse_location(&self.location).map(|parsed| parsed.scheme)
+    }
+
+    /// The bucket or container name, if this location has one (local paths
+    /// don't).
+    pub fn bucket(&self) -> Result<Option<String>, StorageUriParseError> {
+        parse_location(&self.location).map(|parsed| parsed.bucket)
+    }
+
+    /// The object key or path: percent-decoded for locations with a
+    /// bucket/container, kept raw for local (or scheme-less) paths.
+    pub fn key(&self) -> Result<String, StorageUriParseError> {
+        parse_location(&self.location).map(|parsed| parsed.key)
+    }
+
+    /// Render this location in the addressed provider's own native URI
+    /// form, e.g. `gs://bucket/key` or
+    /// `https://account.blob.core.windows.net/container/key`, suitable for
+    /// handing straight to that provider's SDK.
+    pub fn to_native_uri(&self) -> Result<String, StorageUriParseError> {
+        let parsed = parse_location(&self.location)?;
+        let key = utf8_percent_encode(&parsed.key, KEY_ENCODE_SET);
+        Ok(match &parsed.scheme {
+            StorageScheme::S3 => format!("s3://{}/{}", bucket_or_missing(&parsed)?, key),
+            StorageScheme::Gcs => format!("gs://{}/{}", bucket_or_missing(&parsed)?, key),
+            StorageScheme::AzureBlob { account } => format!(
+                "https://{}.blob.core.windows.net/{}/{}",
+                account,
+                bucket_or_missing(&parsed)?,
+                key
+            ),
+            StorageScheme::Custom { endpoint } => {
+                format!("{}/{}/{}", endpoint.trim_end_matches('/'), bucket_or_missing(&parsed)?, key)
+            }
+            StorageScheme::Local => parsed.key.clone(),
+        })
+    }
+}
+
+fn bucket_or_missing(parsed: &ParsedLocation) -> Result<&str, StorageUriParseError> {
+    parsed.bucket.as_deref().ok_or(StorageUriParseError::MissingBucket)
+}
+
+fn parse_location(location: &str) -> Result<ParsedLocation, StorageUriParseError> {
+    if location.is_empty() {
+        return Err(StorageUriParseError::Empty);
+    }
+
+    let Some((scheme, rest)) = location.split_once("://") else {
+        // No scheme: treat as a relative/local path, as-is.
+        return Ok(ParsedLocation {
+            scheme: StorageScheme::Local,
+            bucket: None,
+            key: location.to_string(),
+        });
+    };
+
+    match scheme {
+        "s3" => {
+            let (bucket, key) = split_authority_and_key(rest)?;
+            Ok(ParsedLocation {
+                scheme: StorageScheme::S3,
+                bucket: Some(bucket),
+                key,
+            })
+        }
+        "gs" | "gcs" => {
+            let (bucket, key) = split_authority_and_key(rest)?;
+            Ok(ParsedLocation {
+                scheme: StorageScheme::Gcs,
+                bucket: Some(bucket),
+                key,
+            })
+        }
+        "local" => Ok(ParsedLocation {
+            scheme: StorageScheme::Local,
+            // Kept raw, not percent-decoded, so `StorageUri::parse("a%20b")`
+            // and `StorageUri::parse("local://a%20b")` address the same
+            // local path either way.
+            bucket: None,
+            key: rest.to_string(),
+        }),
+        "http" | "https" => {
+            let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+            if let Some(account) = host.strip_suffix(".blob.core.windows.net") {
+                let (bucket, key) = split_authority_and_key(path)?;
+                Ok(ParsedLocation {
+                    scheme: StorageScheme::AzureBlob {
+                        account: account.to_string(),
+                    },
+                    bucket: Some(bucket),
+                    key,
+                })
+            } else {
+                // An S3-compatible store behind a custom endpoint, e.g.
+                // MinIO or Cloudflare R2: the bucket is the first path
+                // segment, the rest of the path is the key.
+                let (bucket, key) = split_authority_and_key(path)?;
+                Ok(ParsedLocation {
+                    scheme: StorageScheme::Custom {
+                        endpoint: format!("{}://{}", scheme, host),
+                    },
+                    bucket: Some(bucket),
+                    key,
+                })
+            }
+        }
+        other => Err(StorageUriParseError::UnsupportedScheme(other.to_string())),
+    }
+}
+
+/// Split `authority/key/with/slashes` into its bucket and percent-decoded
+/// key, as found after the `scheme://` of an s3/gs location or after the
+/// host of an azure/custom https location.
+fn split_authority_and_key(rest: &str) -> Result<(String, String), StorageUriParseError> {
+    let rest = rest.trim_start_matches('/');
+    let (bucket, key) = rest.split_once('/').unwrap_or((rest, ""));
+    if bucket.is_empty() {
+        return Err(StorageUriParseError::MissingBucket);
+    }
+    Ok((bucket.to_string(), decode_key(key)?))
+}
+
+fn decode_key(key: &str) -> Result<String, StorageUriParseError> {
+    percent_decode_str(key)
+        .decode_utf8()
+        .map(|cow| cow.into_owned())
+        .map_err(|_| StorageUriParseError::InvalidEncoding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_s3_location() {
+        let uri = StorageUri::parse("s3://my-bucket/path/to/object").unwrap();
+        assert_eq!(uri.scheme().unwrap(), StorageScheme::S3);
+        assert_eq!(uri.bucket().unwrap(), Some("my-bucket".to_string()));
+        assert_eq!(uri.key().unwrap(), "path/to/object");
+        assert_eq!(uri.to_native_uri().unwrap(), "s3://my-bucket/path/to/object");
+    }
+
+    #[test]
+    fn parses_gcs_location() {
+        let uri = StorageUri::parse("gs://my-bucket/path/to/object").unwrap();
+        assert_eq!(uri.scheme().unwrap(), StorageScheme::Gcs);
+        assert_eq!(uri.bucket().unwrap(), Some("my-bucket".to_string()));
+        assert_eq!(uri.to_native_uri().unwrap(), "gs://my-bucket/path/to/object");
+    }
+
+    #[test]
+    fn parses_azure_blob_location() {
+        let uri = StorageUri::parse("https://myaccount.blob.core.windows.net/container/path/to/object").unwrap();
+        assert_eq!(
+            uri.scheme().unwrap(),
+            StorageScheme::AzureBlob {
+                account: "myaccount".to_string()
+            }
+        );
+        assert_eq!(uri.bucket().unwrap(), Some("container".to_string()));
+        assert_eq!(uri.key().unwrap(), "path/to/object");
+        assert_eq!(
+            uri.to_native_uri().unwrap(),
+            "https://myaccount.blob.core.windows.net/container/path/to/object"
+        );
+    }
+
+    #[test]
+    fn parses_custom_endpoint_location() {
+        let uri = StorageUri::parse("https://minio.example.com:9000/my-bucket/path/to/object").unwrap();
+        assert_eq!(
+            uri.scheme().unwrap(),
+            StorageScheme::Custom {
+                endpoint: "https://minio.example.com:9000".to_string()
+            }
+        );
+        assert_eq!(uri.bucket().unwrap(), Some("my-bucket".to_string()));
+        assert_eq!(uri.key().unwrap(), "path/to/object");
+    }
+
+    #[test]
+    fn parses_percent_encoded_key() {
+        let uri = StorageUri::parse("s3://my-bucket/path%20with%20spaces/a%2Bb").unwrap();
+        assert_eq!(uri.key().unwrap(), "path with spaces/a+b");
+    }
+
+    #[test]
+    fn missing_scheme_and_local_scheme_agree_on_the_same_path() {
+        let bare = StorageUri::parse("a%20b").unwrap();
+        let local = StorageUri::parse("local://a%20b").unwrap();
+        assert_eq!(bare.scheme().unwrap(), StorageScheme::Local);
+        assert_eq!(local.scheme().unwrap(), StorageScheme::Local);
+        assert_eq!(bare.key().unwrap(), local.key().unwrap());
+        assert_eq!(bare.key().unwrap(), "a%20b");
+    }
+
+    #[test]
+    fn rejects_empty_location() {
+        assert_eq!(StorageUri::parse("").unwrap_err(), StorageUriParseError::Empty);
+    }
+
+    #[test]
+    fn rejects_unsupported_scheme() {
+        assert_eq!(
+            StorageUri::parse("ftp://host/path").unwrap_err(),
+            StorageUriParseError::UnsupportedScheme("ftp".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_missing_bucket() {
+        assert_eq!(StorageUri::parse("s3://").unwrap_err(), StorageUriParseError::MissingBucket);
+    }
+}
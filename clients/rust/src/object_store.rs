@@ -0,0 +1,367 @@
+/// An [`object_store::ObjectStore`] backed by a lakeFS repository and
+/// reference (branch, tag or commit id), so lakeFS can be registered as a
+/// storage backend for DataFusion, delta-rs and other Arrow-ecosystem
+/// consumers without going through a separate connector.
+///
+/// A [`LakeFSObjectStore`] is scoped to a single `(repository, reference)`
+/// pair, the same way `object_store`'s own S3/GCS backends are scoped to a
+/// single bucket; the `object_store::path::Path` passed to every method is
+/// translated directly into the lakeFS object path underneath that pair.
+use std::fmt;
+use std::ops::Range;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::{self, BoxStream, StreamExt};
+
+use object_store::path::Path;
+use object_store::{
+    Attributes, GetOptions, GetRange, GetResult, GetResultPayload, ListResult, MultipartUpload,
+    ObjectMeta, ObjectStore, PutMode, PutMultipartOpts, PutOptions, PutPayload, PutResult,
+    Result as ObjectStoreResult,
+};
+
+use crate::apis::configuration::Configuration;
+use crate::apis::{objects_api, Error as ApiError};
+use crate::models::{self, StorageUri};
+use crate::upload;
+
+/// Adapter from the generated lakeFS client onto `object_store::ObjectStore`.
+pub struct LakeFSObjectStore {
+    configuration: Configuration,
+    repository: String,
+    reference: String,
+}
+
+impl LakeFSObjectStore {
+    pub fn new(configuration: Configuration, repository: impl Into<String>, reference: impl Into<String>) -> Self {
+        Self {
+            configuration,
+            repository: repository.into(),
+            reference: reference.into(),
+        }
+    }
+
+    /// The physical, underlying-storage location of `path`, when lakeFS
+    /// knows one (i.e. the object was written through the staging flow
+    /// and still lives at the address it was linked at).
+    pub async fn physical_address(&self, location: &Path) -> ObjectStoreResult<Option<StorageUri>> {
+        let stat = self.stat(location).await?;
+        Ok(stat.physical_address)
+    }
+
+    async fn stat(&self, location: &Path) -> ObjectStoreResult<models::ObjectStats> {
+        objects_api::stat_object(
+            &self.configuration,
+            &self.repository,
+            &self.reference,
+            location.as_ref(),
+            None,
+            None,
+        )
+        .await
+        .map_err(|err| to_object_store_error(err, location.as_ref()))
+    }
+
+    fn object_meta(location: &Path, stat: &models::ObjectStats) -> ObjectMeta {
+        ObjectMeta {
+            location: location.clone(),
+            last_modified: chrono::DateTime::from_timestamp(stat.mtime, 0).unwrap_or_default(),
+            size: stat.size_bytes.unwrap_or_default() as usize,
+            e_tag: stat.checksum.clone(),
+            version: None,
+        }
+    }
+}
+
+impl fmt::Debug for LakeFSObjectStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LakeFSObjectStore")
+            .field("repository", &self.repository)
+            .field("reference", &self.reference)
+            .finish()
+    }
+}
+
+impl fmt::Display for LakeFSObjectStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LakeFS({}@{})", self.repository, self.reference)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for LakeFSObjectStore {
+    async fn put_opts(&self, location: &Path, payload: PutPayload, opts: PutOptions) -> ObjectStoreResult<PutResult> {
+        match opts.mode {
+            PutMode::Overwrite => {}
+            PutMode::Create | PutMode::Update(_) => {
+                // lakeFS's staging `linkPhysicalAddress` has no conditional
+                // "only if absent" / "only if matching version" primitive,
+                // so this adapter can only honor an unconditional
+                // overwrite. Silently downgrading `Create`/`Update` to an
+                // overwrite would break callers (e.g. delta-rs's
+                // `_delta_log` commit protocol) that rely on it to
+                // serialize concurrent writers.
+                return Err(object_store::Error::NotImplemented);
+            }
+        }
+
+        let bytes = flatten_payload(payload);
+        let result = upload::stage_object(
+            &self.configuration,
+            &self.repository,
+            &self.reference,
+            location.as_ref(),
+            bytes,
+        )
+        .await
+        .map_err(|err| object_store::Error::Generic {
+            store: "LakeFS",
+            source: Box::new(err),
+        })?;
+        Ok(PutResult {
+            e_tag: Some(result.checksum),
+            version: None,
+        })
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        _location: &Path,
+        _opts: PutMultipartOpts,
+    ) -> ObjectStoreResult<Box<dyn MultipartUpload>> {
+        // The lakeFS staging API takes a single PUT to the physical
+        // address; there is no server-side multipart session to open, so
+        // multipart writes are not supported by this adapter.
+        Err(object_store::Error::NotImplemented)
+    }
+
+    async fn get_opts(&self, location: &Path, options: GetOptions) -> ObjectStoreResult<GetResult> {
+        let stat = self.stat(location).await?;
+        let meta = Self::object_meta(location, &stat);
+        let size = meta.size;
+
+        let range_header = options.range.as_ref().map(range_header_value);
+        let bytes = objects_api::get_object(
+            &self.configuration,
+            &self.repository,
+            &self.reference,
+            location.as_ref(),
+            None,
+            None,
+            range_header.as_deref(),
+        )
+        .await
+        .map_err(|err| to_object_store_error(err, location.as_ref()))?;
+
+        let range = options
+            .range
+            .as_ref()
+            .map(|r| resolve_range(r, size))
+            .unwrap_or(0..size);
+
+        Ok(GetResult {
+            payload: GetResultPayload::Stream(stream::once(async move { Ok(Bytes::from(bytes)) }).boxed()),
+            meta,
+            range,
+            attributes: Attributes::default(),
+        })
+    }
+
+    async fn get_range(&self, location: &Path, range: Range<usize>) -> ObjectStoreResult<Bytes> {
+        let header = format!("bytes={}-{}", range.start, range.end.saturating_sub(1));
+        let bytes = objects_api::get_object(
+            &self.configuration,
+            &self.repository,
+            &self.reference,
+            location.as_ref(),
+            None,
+            None,
+            Some(&header),
+        )
+        .await
+        .map_err(|err| to_object_store_error(err, location.as_ref()))?;
+        Ok(Bytes::from(bytes))
+    }
+
+    async fn head(&self, location: &Path) -> ObjectStoreResult<ObjectMeta> {
+        let stat = self.stat(location).await?;
+        Ok(Self::object_meta(location, &stat))
+    }
+
+    async fn delete(&self, location: &Path) -> ObjectStoreResult<()> {
+        objects_api::delete_object(
+            &self.configuration,
+            &self.repository,
+            &self.reference,
+            location.as_ref(),
+        )
+        .await
+        .map_err(|err| to_object_store_error(err, location.as_ref()))
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'_, ObjectStoreResult<ObjectMeta>> {
+        let prefix = prefix.map(|p| p.as_ref().to_string());
+        let configuration = self.configuration.clone();
+        let repository = self.repository.clone();
+        let reference = self.reference.clone();
+
+        stream::unfold(Some(None::<String>), move |after| {
+            let configuration = configuration.clone();
+            let repository = repository.clone();
+            let reference = reference.clone();
+            let prefix = prefix.clone();
+            async move {
+                let after = after?;
+                let page = objects_api::list_objects(
+                    &configuration,
+                    &repository,
+                    &reference,
+                    None,
+                    after.as_deref(),
+                    None,
+                    prefix.as_deref(),
+                    None,
+                )
+                .await
+                .map_err(|err| to_object_store_error(err, prefix.as_deref().unwrap_or_default()));
+
+                let page = match page {
+                    Ok(page) => page,
+                    Err(err) => return Some((stream::iter(vec![Err(err)]).boxed(), None)),
+                };
+
+                let next_after = if page.pagination.has_more {
+                    Some(Some(page.pagination.next_offset))
+                } else {
+                    None
+                };
+
+                let entries: Vec<ObjectStoreResult<ObjectMeta>> = page
+                    .results
+                    .into_iter()
+                    .map(|obj| {
+                        let location = Path::from(obj.path);
+                        Ok(LakeFSObjectStore::object_meta(&location, &obj))
+                    })
+                    .collect();
+
+                Some((stream::iter(entries).boxed(), next_after))
+            }
+        })
+        .flatten()
+        .boxed()
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> ObjectStoreResult<ListResult> {
+        let mut common_prefixes = Vec::new();
+        let mut objects = Vec::new();
+        let mut after: Option<String> = None;
+
+        loop {
+            let page = objects_api::list_objects(
+                &self.configuration,
+                &self.repository,
+                &self.reference,
+                Some("/"),
+                after.as_deref(),
+                None,
+                prefix.map(|p| p.as_ref()),
+                None,
+            )
+            .await
+            .map_err(|err| to_object_store_error(err, prefix.map(|p| p.as_ref()).unwrap_or_default()))?;
+
+            for obj in &page.results {
+                let location = Path::from(obj.path.clone());
+                objects.push(Self::object_meta(&location, obj));
+            }
+            for common in page.pagination.common_prefixes.unwrap_or_default() {
+                common_prefixes.push(Path::from(common));
+            }
+
+            if page.pagination.has_more {
+                after = Some(page.pagination.next_offset);
+            } else {
+                break;
+            }
+        }
+
+        Ok(ListResult {
+            common_prefixes,
+            objects,
+        })
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> ObjectStoreResult<()> {
+        let bytes = objects_api::get_object(
+            &self.configuration,
+            &self.repository,
+            &self.reference,
+            from.as_ref(),
+            None,
+            None,
+            None,
+        )
+        .await
+        .map_err(|err| to_object_store_error(err, from.as_ref()))?;
+        self.put(to, PutPayload::from(Bytes::from(bytes))).await.map(|_| ())
+    }
+
+    async fn copy_if_not_exists(&self, _from: &Path, _to: &Path) -> ObjectStoreResult<()> {
+        // `ObjectStore::copy_if_not_exists` is documented as an atomic
+        // conditional-copy primitive (callers use it for locking), but
+        // lakeFS's staging API has no equivalent operation to back that
+        // with — a head-then-copy here would only be a race, not the
+        // atomic guarantee the trait promises. Report it as unsupported
+        // rather than silently provide a weaker guarantee.
+        Err(object_store::Error::NotImplemented)
+    }
+}
+
+/// Flatten a (possibly chunked) `PutPayload` into the single contiguous
+/// buffer the staging upload flow PUTs in one shot.
+fn flatten_payload(payload: PutPayload) -> Bytes {
+    if let [single] = payload.as_ref() {
+        return single.clone();
+    }
+    let mut buf = Vec::with_capacity(payload.content_length());
+    for chunk in payload.as_ref() {
+        buf.extend_from_slice(chunk);
+    }
+    Bytes::from(buf)
+}
+
+fn range_header_value(range: &GetRange) -> String {
+    match range {
+        GetRange::Bounded(range) => format!("bytes={}-{}", range.start, range.end.saturating_sub(1)),
+        GetRange::Offset(offset) => format!("bytes={}-", offset),
+        GetRange::Suffix(length) => format!("bytes=-{}", length),
+    }
+}
+
+fn resolve_range(range: &GetRange, size: usize) -> Range<usize> {
+    match range {
+        GetRange::Bounded(range) => range.start..range.end.min(size),
+        GetRange::Offset(offset) => (*offset).min(size)..size,
+        GetRange::Suffix(length) => size.saturating_sub(*length)..size,
+    }
+}
+
+fn to_object_store_error<E: std::error::Error + Send + Sync + 'static>(
+    err: ApiError<E>,
+    path: &str,
+) -> object_store::Error {
+    match &err {
+        ApiError::ResponseError(response) if response.status == reqwest::StatusCode::NOT_FOUND => {
+            object_store::Error::NotFound {
+                path: path.to_string(),
+                source: Box::new(err),
+            }
+        }
+        _ => object_store::Error::Generic {
+            store: "LakeFS",
+            source: Box::new(err),
+        },
+    }
+}
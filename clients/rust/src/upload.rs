@@ -0,0 +1,289 @@
+/// High-level, two-phase "physical address" upload.
+///
+/// lakeFS can hand out a direct, pre-addressed location in the underlying
+/// object store (an S3/GCS/Azure key, see [`crate::models::StorageUri`])
+/// instead of requiring object bytes to be streamed through the lakeFS
+/// gateway. This module implements that staging flow end to end:
+///
+/// 1. `getPhysicalAddress` to obtain a [`models::StagingLocation`] for the
+///    target `(repository, branch, path)`.
+/// 2. PUT the object bytes straight to the underlying storage location
+///    (the presigned URL when one is returned, otherwise the physical
+///    address itself).
+/// 3. `linkPhysicalAddress` with the same staging location plus the
+///    checksum and size computed from the bytes that were just uploaded.
+///
+/// The staging token embedded in the physical address is only valid until
+/// the branch head moves. If another write races ahead of us, lakeFS
+/// rejects the link call and we have to fetch a fresh physical address and
+/// redo the upload+link; this is handled transparently up to
+/// [`MAX_STALE_ADDRESS_RETRIES`] attempts.
+use bytes::Bytes;
+
+use crate::apis::configuration::Configuration;
+use crate::apis::staging_api;
+use crate::apis::Error as ApiError;
+use crate::models::{StagingLocation, StagingMetadata, StorageUri};
+
+/// Maximum number of upload attempts, the first one included. Each time
+/// the lakeFS server reports that the staging token is stale, a fresh
+/// physical address is fetched and the upload retried, so there are at
+/// most `MAX_STALE_ADDRESS_RETRIES - 1` retries.
+const MAX_STALE_ADDRESS_RETRIES: u32 = 3;
+
+/// Outcome of a successful staged upload.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UploadResult {
+    /// Repository path the object was linked into.
+    pub path: String,
+    /// Physical, underlying-storage location the bytes were written to.
+    pub physical_address: StorageUri,
+    /// Checksum the storage provider reported (its `ETag` response header)
+    /// for the uploaded bytes.
+    pub checksum: String,
+    /// Size in bytes of the uploaded object.
+    pub size_bytes: i64,
+}
+
+/// Errors that can occur while staging an object directly to the
+/// underlying storage.
+#[derive(Debug)]
+pub enum UploadError {
+    /// The `getPhysicalAddress` call failed.
+    GetPhysicalAddress(ApiError<staging_api::GetPhysicalAddressError>),
+    /// The `linkPhysicalAddress` call failed.
+    LinkPhysicalAddress(ApiError<staging_api::LinkPhysicalAddressError>),
+    /// The PUT to the underlying storage location failed.
+    Put(reqwest::Error),
+    /// The storage provider's PUT response carried no `ETag` header, so
+    /// there is no checksum to submit on link.
+    MissingChecksum,
+    /// The staging token kept going stale faster than we could upload.
+    StaleAddressRetriesExhausted { attempts: u32 },
+}
+
+impl std::fmt::Display for UploadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UploadError::GetPhysicalAddress(e) => write!(f, "getPhysicalAddress failed: {}", e),
+            UploadError::LinkPhysicalAddress(e) => write!(f, "linkPhysicalAddress failed: {}", e),
+            UploadError::Put(e) => write!(f, "upload to physical address failed: {}", e),
+            UploadError::MissingChecksum => write!(f, "storage provider returned no ETag to use as a checksum"),
+            UploadError::StaleAddressRetriesExhausted { attempts } => write!(
+                f,
+                "staging token kept going stale after {} attempt(s)",
+                attempts
+            ),
+        }
+    }
+}
+
+impl std::error::Error for UploadError {}
+
+/// Upload `data` to `repository`/`branch`/`path` via the staging flow,
+/// blocking the current thread until the object is linked.
+pub fn stage_object_blocking(
+    configuration: &Configuration,
+    repository: &str,
+    branch: &str,
+    path: &str,
+    data: &[u8],
+) -> Result<UploadResult, UploadError> {
+    let client = reqwest::blocking::Client::new();
+    let size_bytes = data.len() as i64;
+
+    for attempt in 1..=MAX_STALE_ADDRESS_RETRIES {
+        // `presign: Some(true)` so `physical_address.location` (a native
+        // `s3://`/`gs://` URI, not something reqwest can PUT to) is never
+        // our only option: without presigning we'd have nothing HTTP-reachable
+        // to upload to on backends that don't default presigning on.
+        let staging = staging_api::get_physical_address(configuration, repository, branch, path, Some(true))
+            .map_err(UploadError::GetPhysicalAddress)?;
+
+        let put_target = staging
+            .presigned_url
+            .clone()
+            .unwrap_or_else(|| staging.physical_address.location.clone());
+        let response = client
+            .put(&put_target)
+            .body(data.to_vec())
+            .send()
+            .map_err(UploadError::Put)?;
+        if let Err(err) = response.error_for_status_ref() {
+            return Err(UploadError::Put(err));
+        }
+        let checksum = extract_checksum(response.headers())?;
+
+        let metadata = StagingMetadata {
+            staging: staging.clone(),
+            checksum: checksum.clone(),
+            size_bytes,
+            mtime: None,
+            content_type: None,
+        };
+
+        match staging_api::link_physical_address(configuration, repository, branch, path, Some(metadata)) {
+            Ok(_) => {
+                return Ok(UploadResult {
+                    path: path.to_string(),
+                    physical_address: staging.physical_address,
+                    checksum,
+                    size_bytes,
+                })
+            }
+            Err(err) if is_stale_staging_token(&err) => {
+                if attempt == MAX_STALE_ADDRESS_RETRIES {
+                    return Err(UploadError::StaleAddressRetriesExhausted { attempts: attempt });
+                }
+                continue;
+            }
+            Err(err) => return Err(UploadError::LinkPhysicalAddress(err)),
+        }
+    }
+
+    unreachable!("loop above always returns on its last iteration")
+}
+
+/// Async equivalent of [`stage_object_blocking`].
+pub async fn stage_object(
+    configuration: &Configuration,
+    repository: &str,
+    branch: &str,
+    path: &str,
+    data: Bytes,
+) -> Result<UploadResult, UploadError> {
+    let client = reqwest::Client::new();
+    let size_bytes = data.len() as i64;
+
+    for attempt in 1..=MAX_STALE_ADDRESS_RETRIES {
+        // See the matching comment in `stage_object_blocking`: presigning
+        // must be requested explicitly or `physical_address.location` (a
+        // native `s3://`/`gs://` URI) may be the only target we get back.
+        let staging =
+            staging_api::get_physical_address(configuration, repository, branch, path, Some(true))
+                .await
+                .map_err(UploadError::GetPhysicalAddress)?;
+
+        let put_target = staging
+            .presigned_url
+            .clone()
+            .unwrap_or_else(|| staging.physical_address.location.clone());
+        let response = client
+            .put(&put_target)
+            .body(data.clone())
+            .send()
+            .await
+            .map_err(UploadError::Put)?;
+        if !response.status().is_success() {
+            return Err(UploadError::Put(
+                response
+                    .error_for_status()
+                    .expect_err("checked status is an error above"),
+            ));
+        }
+        let checksum = extract_checksum(response.headers())?;
+
+        let metadata = StagingMetadata {
+            staging: staging.clone(),
+            checksum: checksum.clone(),
+            size_bytes,
+            mtime: None,
+            content_type: None,
+        };
+
+        match staging_api::link_physical_address(configuration, repository, branch, path, Some(metadata))
+            .await
+        {
+            Ok(_) => {
+                return Ok(UploadResult {
+                    path: path.to_string(),
+                    physical_address: staging.physical_address,
+                    checksum,
+                    size_bytes,
+                })
+            }
+            Err(err) if is_stale_staging_token(&err) => {
+                if attempt == MAX_STALE_ADDRESS_RETRIES {
+                    return Err(UploadError::StaleAddressRetriesExhausted { attempts: attempt });
+                }
+                continue;
+            }
+            Err(err) => return Err(UploadError::LinkPhysicalAddress(err)),
+        }
+    }
+
+    unreachable!("loop above always returns on its last iteration")
+}
+
+/// Pull the checksum the storage provider computed for the bytes we just
+/// PUT out of its `ETag` response header, rather than assuming a hash
+/// algorithm (S3's ETag is an MD5 digest for single-part uploads, but
+/// GCS's and Azure's are not, so we can't compute this ourselves and have
+/// it match what the provider recorded).
+fn extract_checksum(headers: &reqwest::header::HeaderMap) -> Result<String, UploadError> {
+    headers
+        .get(reqwest::header::ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(|etag| etag.trim_matches('"').to_string())
+        .ok_or(UploadError::MissingChecksum)
+}
+
+/// A concurrent write moved the branch head out from under us: lakeFS
+/// rejects the link with a conflict because the staging token we hold no
+/// longer matches. The caller should re-fetch a fresh physical address.
+fn is_stale_staging_token(err: &ApiError<staging_api::LinkPhysicalAddressError>) -> bool {
+    matches!(
+        err,
+        ApiError::ResponseError(response) if response.status == reqwest::StatusCode::CONFLICT
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apis::ResponseContent;
+
+    fn headers_with_etag(value: &str) -> reqwest::header::HeaderMap {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::ETAG, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn extract_checksum_trims_quotes() {
+        let checksum = extract_checksum(&headers_with_etag("\"abc123\"")).unwrap();
+        assert_eq!(checksum, "abc123");
+    }
+
+    #[test]
+    fn extract_checksum_accepts_unquoted_etag() {
+        let checksum = extract_checksum(&headers_with_etag("abc123")).unwrap();
+        assert_eq!(checksum, "abc123");
+    }
+
+    #[test]
+    fn extract_checksum_errors_without_etag() {
+        let headers = reqwest::header::HeaderMap::new();
+        assert!(matches!(extract_checksum(&headers), Err(UploadError::MissingChecksum)));
+    }
+
+    #[test]
+    fn stale_staging_token_is_a_conflict_response() {
+        let err: ApiError<staging_api::LinkPhysicalAddressError> = ApiError::ResponseError(ResponseContent {
+            status: reqwest::StatusCode::CONFLICT,
+            content: String::new(),
+            entity: None,
+        });
+        assert!(is_stale_staging_token(&err));
+    }
+
+    #[test]
+    fn non_conflict_response_is_not_a_stale_staging_token() {
+        let err: ApiError<staging_api::LinkPhysicalAddressError> = ApiError::ResponseError(ResponseContent {
+            status: reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+            content: String::new(),
+            entity: None,
+        });
+        assert!(!is_stale_staging_token(&err));
+    }
+}